@@ -0,0 +1,133 @@
+//! Generates config files for the processes this crate launches.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use portpicker::Port;
+
+use crate::network::ActivationHeights;
+
+pub(crate) const ZCASHD_FILENAME: &str = "zcashd.conf";
+pub(crate) const ZAINOD_FILENAME: &str = "zainod.toml";
+pub(crate) const ZEBRAD_FILENAME: &str = "zebrad.toml";
+
+/// Writes a `zcashd.conf` to `out_dir` and returns its path.
+pub(crate) fn zcashd(
+    out_dir: &Path,
+    rpc_port: Port,
+    activation_heights: &ActivationHeights,
+    miner_address: Option<&str>,
+) -> std::io::Result<PathBuf> {
+    let mut config = String::new();
+    config.push_str("regtest=1\n");
+    config.push_str("rpcuser=xxxxxx\n");
+    config.push_str("rpcpassword=xxxxxx\n");
+    config.push_str(&format!("rpcport={rpc_port}\n"));
+
+    if let Some(height) = activation_heights.overwinter {
+        config.push_str(&format!("nuparams=5ba81b19:{height}\n"));
+    }
+    if let Some(height) = activation_heights.sapling {
+        config.push_str(&format!("nuparams=76b809bb:{height}\n"));
+    }
+    if let Some(height) = activation_heights.blossom {
+        config.push_str(&format!("nuparams=2bb40e60:{height}\n"));
+    }
+    if let Some(height) = activation_heights.heartwood {
+        config.push_str(&format!("nuparams=f5b9230b:{height}\n"));
+    }
+    if let Some(height) = activation_heights.canopy {
+        config.push_str(&format!("nuparams=e9ff75a6:{height}\n"));
+    }
+    if let Some(height) = activation_heights.nu5 {
+        config.push_str(&format!("nuparams=c2d6d0b4:{height}\n"));
+    }
+
+    if let Some(miner_address) = miner_address {
+        config.push_str(&format!("mineraddress={miner_address}\n"));
+    }
+
+    let config_file_path = out_dir.join(ZCASHD_FILENAME);
+    let mut file = std::fs::File::create(&config_file_path)?;
+    file.write_all(config.as_bytes())?;
+
+    Ok(config_file_path)
+}
+
+/// Writes a `zainod.toml` to `out_dir` and returns its path.
+pub(crate) fn zainod(
+    out_dir: &Path,
+    listen_port: Port,
+    validator_port: Port,
+) -> std::io::Result<PathBuf> {
+    let config = format!(
+        "listen_address = \"127.0.0.1:{listen_port}\"\n\
+         validator_address = \"127.0.0.1:{validator_port}\"\n\
+         validator_user = \"xxxxxx\"\n\
+         validator_password = \"xxxxxx\"\n"
+    );
+
+    let config_file_path = out_dir.join(ZAINOD_FILENAME);
+    let mut file = std::fs::File::create(&config_file_path)?;
+    file.write_all(config.as_bytes())?;
+
+    Ok(config_file_path)
+}
+
+/// Writes a `zebrad.toml` to `out_dir` and returns its path.
+///
+/// `cache_dir` is zebrad's own chain-state directory, not `out_dir` (which only holds this
+/// generated config file).
+pub(crate) fn zebrad(
+    out_dir: &Path,
+    rpc_port: Port,
+    activation_heights: &ActivationHeights,
+    cache_dir: &Path,
+) -> std::io::Result<PathBuf> {
+    let mut config = String::new();
+    config.push_str("[network]\n");
+    config.push_str("network = \"Regtest\"\n\n");
+    config.push_str("[state]\n");
+    config.push_str(&format!("cache_dir = \"{}\"\n", cache_dir.display()));
+    config.push_str("ephemeral = false\n\n");
+    config.push_str("[rpc]\n");
+    config.push_str(&format!("listen_addr = \"127.0.0.1:{rpc_port}\"\n"));
+    // rpc::call and zainod's validator_user/validator_password send no cookie, so auth must be
+    // off - otherwise zebrad 401s every request once a listen_addr is configured.
+    config.push_str("enable_cookie_auth = false\n");
+
+    if activation_heights.nu5.is_some()
+        || activation_heights.canopy.is_some()
+        || activation_heights.heartwood.is_some()
+        || activation_heights.blossom.is_some()
+        || activation_heights.sapling.is_some()
+        || activation_heights.overwinter.is_some()
+    {
+        // Regtest activation heights, not a testnet - the [network] table itself holds them.
+        config.push_str("\n[network.activation_heights]\n");
+        if let Some(height) = activation_heights.overwinter {
+            config.push_str(&format!("Overwinter = {height}\n"));
+        }
+        if let Some(height) = activation_heights.sapling {
+            config.push_str(&format!("Sapling = {height}\n"));
+        }
+        if let Some(height) = activation_heights.blossom {
+            config.push_str(&format!("Blossom = {height}\n"));
+        }
+        if let Some(height) = activation_heights.heartwood {
+            config.push_str(&format!("Heartwood = {height}\n"));
+        }
+        if let Some(height) = activation_heights.canopy {
+            config.push_str(&format!("Canopy = {height}\n"));
+        }
+        if let Some(height) = activation_heights.nu5 {
+            config.push_str(&format!("NU5 = {height}\n"));
+        }
+    }
+
+    let config_file_path = out_dir.join(ZEBRAD_FILENAME);
+    let mut file = std::fs::File::create(&config_file_path)?;
+    file.write_all(config.as_bytes())?;
+
+    Ok(config_file_path)
+}
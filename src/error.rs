@@ -0,0 +1,43 @@
+//! Error types returned when launching local processes.
+
+use std::process::ExitStatus;
+
+use portpicker::Port;
+
+/// Errors that can occur while launching a local process (`zcashd`, `zainod`, ...).
+#[derive(Debug, thiserror::Error)]
+pub enum LaunchError {
+    /// The process exited before reporting that it was ready.
+    #[error(
+        "{process_name} failed to launch.\nexit status: {exit_status}\nstdout: {stdout}\nstderr: {stderr}"
+    )]
+    ProcessFailed {
+        process_name: String,
+        exit_status: ExitStatus,
+        stdout: String,
+        stderr: String,
+    },
+
+    /// The process's readiness pattern never appeared within the configured deadline.
+    #[error(
+        "{process_name} did not report readiness within {timeout:?}.\nstdout: {stdout}\nstderr: {stderr}"
+    )]
+    Timeout {
+        process_name: String,
+        timeout: std::time::Duration,
+        stdout: String,
+        stderr: String,
+    },
+
+    /// The requested port was already taken by another process.
+    ///
+    /// When launch was given a random port, it first retries with a freshly picked port, up to
+    /// a fixed number of times; this only reaches the caller if every retry collides too. With
+    /// a *fixed* port it is returned on the first collision.
+    #[error("{process_name} could not bind port {port}: address already in use")]
+    PortInUse { process_name: String, port: Port },
+
+    /// The data directory is already locked by another running instance.
+    #[error("{process_name} could not start: lock file held on data directory")]
+    LockFileHeld { process_name: String },
+}
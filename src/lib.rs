@@ -1,25 +1,88 @@
-use std::{fs::File, io::Read, path::PathBuf, process::Child};
+use std::{
+    ffi::{OsStr, OsString},
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    process::Child,
+};
 
 use error::LaunchError;
 use getset::Getters;
 use network::ActivationHeights;
 use portpicker::Port;
+use readiness::ReadinessConfig;
 use tempfile::TempDir;
 
 pub(crate) mod config;
 pub mod error;
 pub mod network;
+pub mod process;
+pub mod readiness;
+pub(crate) mod rpc;
 pub(crate) mod utils;
 
+pub use process::LocalProcess;
+
 const STDOUT_LOG: &str = "stdout.log";
 
+/// Kills `handle` and waits for it to exit, returning its exit status for error reporting.
+/// Used when a process must be torn down mid-launch (fatal log line, readiness timeout).
+fn kill_and_wait(handle: &mut Child, process_name: &str) -> std::process::ExitStatus {
+    if let Err(e) = handle.kill() {
+        tracing::warn!("{process_name} has already terminated: {e}");
+    }
+    match handle.wait() {
+        Ok(exit_status) => exit_status,
+        Err(e) => panic!("Unexpected Error: {e}"),
+    }
+}
+
+/// A process's chain-state directory: either a throwaway directory removed when the owning
+/// process is dropped, or a caller-supplied path reused across launches to cache chain state.
+pub enum DataDir {
+    /// Deleted once the owning process is dropped.
+    Temp(TempDir),
+    /// Left on disk when the owning process is dropped, so a later launch can reuse it. Call
+    /// [`reset_data_dir`] to deliberately discard it instead.
+    Persistent(PathBuf),
+}
+
+impl DataDir {
+    fn path(&self) -> &Path {
+        match self {
+            DataDir::Temp(dir) => dir.path(),
+            DataDir::Persistent(path) => path,
+        }
+    }
+
+    fn for_launch(persistent_path: Option<PathBuf>) -> std::io::Result<DataDir> {
+        match persistent_path {
+            Some(path) => {
+                std::fs::create_dir_all(&path)?;
+                Ok(DataDir::Persistent(path))
+            }
+            None => Ok(DataDir::Temp(tempfile::tempdir()?)),
+        }
+    }
+}
+
+/// Deletes everything under `path`, for callers that want to deliberately discard a
+/// [`DataDir::Persistent`] cache rather than reusing it on the next launch.
+pub fn reset_data_dir(path: impl AsRef<Path>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if path.exists() {
+        std::fs::remove_dir_all(path)?;
+    }
+    std::fs::create_dir_all(path)
+}
+
 /// Struct associated with Zcashd process.
 #[derive(Getters)]
 #[getset(get = "pub")]
 pub struct Zcashd {
     handle: Child,
     port: Port,
-    _data_dir: TempDir,
+    _data_dir: DataDir,
     logs_dir: TempDir,
     config_dir: TempDir,
     zcash_cli_bin: Option<PathBuf>,
@@ -35,97 +98,75 @@ impl Zcashd {
     ///
     /// Use `activation_heights` to specify custom network upgrade activation heights
     ///
-    /// Use `miner_address` to specify the target address for the block rewards when blocks are generated.  
+    /// Use `miner_address` to specify the target address for the block rewards when blocks are generated.
+    ///
+    /// Use `readiness` to customize how launch detects that zcashd is ready (or has failed) and
+    /// how long to wait before giving up. `None` falls back to [`ReadinessConfig::zcashd`].
+    ///
+    /// When `rpc_port` is `None`, a port collision or stale lock file is retried with a freshly
+    /// picked port up to [`utils::MAX_RANDOM_PORT_RETRIES`] times. With a fixed `rpc_port`,
+    /// [`LaunchError::PortInUse`] / [`LaunchError::LockFileHeld`] are returned directly.
     pub fn launch(
         zcashd_bin: Option<PathBuf>,
         zcash_cli_bin: Option<PathBuf>,
         rpc_port: Option<Port>,
         activation_heights: &ActivationHeights,
         miner_address: Option<&str>,
+        readiness: Option<ReadinessConfig>,
+    ) -> Result<Zcashd, LaunchError> {
+        let readiness = readiness.unwrap_or_else(ReadinessConfig::zcashd);
+        utils::retry_on_port_collision(rpc_port, |port| {
+            Self::launch_once(
+                zcashd_bin.clone(),
+                zcash_cli_bin.clone(),
+                port,
+                activation_heights,
+                miner_address,
+                &readiness,
+                &[],
+                &[],
+                None,
+            )
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn launch_once(
+        zcashd_bin: Option<PathBuf>,
+        zcash_cli_bin: Option<PathBuf>,
+        port: Port,
+        activation_heights: &ActivationHeights,
+        miner_address: Option<&str>,
+        readiness: &ReadinessConfig,
+        extra_args: &[OsString],
+        env: &[(OsString, OsString)],
+        persistent_data_dir: Option<PathBuf>,
     ) -> Result<Zcashd, LaunchError> {
-        let port = utils::pick_unused_port(rpc_port);
         let config_dir = tempfile::tempdir().unwrap();
         let config_file_path =
             config::zcashd(config_dir.path(), port, activation_heights, miner_address).unwrap();
 
-        let data_dir = tempfile::tempdir().unwrap();
+        let data_dir = DataDir::for_launch(persistent_data_dir).unwrap();
 
         let mut command = match zcashd_bin {
             Some(path) => std::process::Command::new(path),
             None => std::process::Command::new("zcashd"),
         };
+
+        let mut conf_arg = OsString::from("--conf=");
+        conf_arg.push(&config_file_path);
+        let mut datadir_arg = OsString::from("--datadir=");
+        datadir_arg.push(data_dir.path());
+
         command
-            .args([
-                "--printtoconsole",
-                format!(
-                    "--conf={}",
-                    config_file_path.to_str().expect("should be valid UTF-8")
-                )
-                .as_str(),
-                format!(
-                    "--datadir={}",
-                    data_dir.path().to_str().expect("should be valid UTF-8")
-                )
-                .as_str(),
-                "-debug=1",
-            ])
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
-
-        let mut handle = command.spawn().unwrap();
-
-        let logs_dir = tempfile::tempdir().unwrap();
-        let stdout_log_path = logs_dir.path().join(STDOUT_LOG);
-        let mut stdout_log = File::create(&stdout_log_path).unwrap();
-        let mut stdout = handle.stdout.take().unwrap();
-        // TODO: consider writing logs in a runtime to increase performance
-        std::thread::spawn(move || {
-            std::io::copy(&mut stdout, &mut stdout_log)
-                .expect("should be able to read/write stdout log");
-        });
-
-        let mut stdout_log = File::open(stdout_log_path).expect("should be able to open log");
-        let mut stdout = String::new();
-
-        let check_interval = std::time::Duration::from_millis(100);
-
-        // wait for stdout log entry that indicates daemon is ready
-        loop {
-            match handle.try_wait() {
-                Ok(Some(exit_status)) => {
-                    stdout_log.read_to_string(&mut stdout).unwrap();
-
-                    let mut stderr = String::new();
-                    handle
-                        .stderr
-                        .take()
-                        .unwrap()
-                        .read_to_string(&mut stderr)
-                        .unwrap();
-
-                    return Err(LaunchError::ProcessFailed {
-                        process_name: "zcashd".to_string(),
-                        exit_status,
-                        stdout,
-                        stderr,
-                    });
-                }
-                Ok(None) => (),
-                Err(e) => {
-                    panic!("Unexpected Error: {e}")
-                }
-            };
-
-            stdout_log.read_to_string(&mut stdout).unwrap();
-            if stdout.contains("Error:") {
-                panic!("Zcashd launch failed without reporting an error code!\nexiting with panic. you may have to shut the daemon down manually.");
-            } else if stdout.contains("init message: Done loading") {
-                // launch successful
-                break;
-            }
+            .arg("--printtoconsole")
+            .arg(conf_arg)
+            .arg(datadir_arg)
+            .arg("-debug=1")
+            .args(extra_args)
+            .envs(env.iter().map(|(k, v)| (k, v)));
 
-            std::thread::sleep(check_interval);
-        }
+        let (handle, logs_dir) = process::spawn_and_await(command, "zcashd", port, readiness)?;
 
         Ok(Zcashd {
             handle,
@@ -158,8 +199,136 @@ impl Zcashd {
         command.args(args).output()
     }
 
+    /// Generate `num_blocks` blocks.
+    pub fn generate_blocks(&self, num_blocks: u32) -> std::io::Result<std::process::Output> {
+        self.zcash_cli_command(&["generate", &num_blocks.to_string()])
+    }
+}
+
+/// Builder for launching a [`Zcashd`], for callers that need more than
+/// [`Zcashd::launch`]'s fixed set of parameters - e.g. extra node flags or environment
+/// variables this crate doesn't model yet.
+#[derive(Default)]
+pub struct ZcashdBuilder {
+    zcashd_bin: Option<PathBuf>,
+    zcash_cli_bin: Option<PathBuf>,
+    rpc_port: Option<Port>,
+    activation_heights: ActivationHeights,
+    miner_address: Option<String>,
+    readiness: Option<ReadinessConfig>,
+    extra_args: Vec<OsString>,
+    env: Vec<(OsString, OsString)>,
+    data_dir: Option<PathBuf>,
+}
+
+impl ZcashdBuilder {
+    /// Starts a new builder with no binaries, fixed port, or extra args set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path to the `zcashd` binary. Defaults to running "zcashd" from $PATH.
+    pub fn zcashd_bin(mut self, path: impl AsRef<OsStr>) -> Self {
+        self.zcashd_bin = Some(PathBuf::from(path.as_ref()));
+        self
+    }
+
+    /// Path to the `zcash-cli` binary. Defaults to running "zcash-cli" from $PATH.
+    pub fn zcash_cli_bin(mut self, path: impl AsRef<OsStr>) -> Self {
+        self.zcash_cli_bin = Some(PathBuf::from(path.as_ref()));
+        self
+    }
+
+    /// Fixed RPC port. Defaults to a randomly picked port.
+    pub fn rpc_port(mut self, port: Port) -> Self {
+        self.rpc_port = Some(port);
+        self
+    }
+
+    /// Custom network upgrade activation heights.
+    pub fn activation_heights(mut self, activation_heights: ActivationHeights) -> Self {
+        self.activation_heights = activation_heights;
+        self
+    }
+
+    /// Target address for block rewards when blocks are generated.
+    pub fn miner_address(mut self, miner_address: impl Into<String>) -> Self {
+        self.miner_address = Some(miner_address.into());
+        self
+    }
+
+    /// Overrides how launch detects readiness/failure and how long it waits before giving up.
+    pub fn readiness(mut self, readiness: ReadinessConfig) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    /// Appends extra command-line arguments passed to `zcashd` as-is, for flags this builder
+    /// doesn't model yet.
+    pub fn extra_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.extra_args
+            .extend(args.into_iter().map(|s| s.as_ref().to_os_string()));
+        self
+    }
+
+    /// Sets an environment variable on the spawned `zcashd` process.
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.env
+            .push((key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+        self
+    }
+
+    /// Reuses a caller-supplied, persistent data directory across launches instead of a
+    /// throwaway one, so chain state survives between runs. The directory is created if it
+    /// doesn't already exist and is left on disk when this [`Zcashd`] is dropped - call
+    /// [`reset_data_dir`] to deliberately wipe it.
+    pub fn data_dir(mut self, path: impl AsRef<OsStr>) -> Self {
+        self.data_dir = Some(PathBuf::from(path.as_ref()));
+        self
+    }
+
+    /// Launches `zcashd` with the configured options.
+    pub fn spawn(self) -> Result<Zcashd, LaunchError> {
+        let readiness = self.readiness.unwrap_or_else(ReadinessConfig::zcashd);
+        utils::retry_on_port_collision(self.rpc_port, |port| {
+            Zcashd::launch_once(
+                self.zcashd_bin.clone(),
+                self.zcash_cli_bin.clone(),
+                port,
+                &self.activation_heights,
+                self.miner_address.as_deref(),
+                &readiness,
+                &self.extra_args,
+                &self.env,
+                self.data_dir.clone(),
+            )
+        })
+    }
+}
+
+impl LocalProcess for Zcashd {
+    fn handle(&mut self) -> &mut Child {
+        &mut self.handle
+    }
+
+    fn logs_dir(&self) -> &TempDir {
+        &self.logs_dir
+    }
+
+    fn port(&self) -> Port {
+        self.port
+    }
+
+    fn process_name(&self) -> &'static str {
+        "zcashd"
+    }
+
     /// Stops the Zcashd process.
-    pub fn stop(&mut self) {
+    fn stop(&mut self) {
         match self.zcash_cli_command(&["stop"]) {
             Ok(_) => {
                 if let Err(e) = self.handle.wait() {
@@ -179,27 +348,13 @@ impl Zcashd {
             }
         }
     }
-
-    /// Generate `num_blocks` blocks.
-    pub fn generate_blocks(&self, num_blocks: u32) -> std::io::Result<std::process::Output> {
-        self.zcash_cli_command(&["generate", &num_blocks.to_string()])
-    }
-
-    /// Prints the stdout log.
-    pub fn print_stdout(&self) {
-        let stdout_log_path = self.logs_dir.path().join(STDOUT_LOG);
-        let mut stdout_log = File::open(stdout_log_path).expect("should be able to open log");
-        let mut stdout = String::new();
-        stdout_log.read_to_string(&mut stdout).unwrap();
-        println!("{}", stdout);
-    }
 }
 
 impl Default for Zcashd {
     /// Default launch for Zcashd.
     /// Panics on failure.
     fn default() -> Self {
-        Zcashd::launch(None, None, None, &ActivationHeights::default(), None).unwrap()
+        Zcashd::launch(None, None, None, &ActivationHeights::default(), None, None).unwrap()
     }
 }
 
@@ -225,12 +380,41 @@ impl Zainod {
     /// Use `fixed_port` to specify a port for Zainod. Otherwise, a port is picked at random.
     ///
     /// The `validator_port` must be specified and the validator process must be running before launching Zainod.
+    ///
+    /// Use `readiness` to customize how launch detects that zainod is ready (or has failed) and
+    /// how long to wait before giving up. `None` falls back to [`ReadinessConfig::zainod`].
+    ///
+    /// When `listen_port` is `None`, a port collision or stale lock file is retried with a
+    /// freshly picked port up to [`utils::MAX_RANDOM_PORT_RETRIES`] times. With a fixed
+    /// `listen_port`, [`LaunchError::PortInUse`] / [`LaunchError::LockFileHeld`] are returned
+    /// directly.
     pub fn launch(
         zainod_bin: Option<PathBuf>,
         listen_port: Option<Port>,
         validator_port: Port,
+        readiness: Option<ReadinessConfig>,
+    ) -> Result<Zainod, LaunchError> {
+        let readiness = readiness.unwrap_or_else(ReadinessConfig::zainod);
+        utils::retry_on_port_collision(listen_port, |port| {
+            Self::launch_once(
+                zainod_bin.clone(),
+                port,
+                validator_port,
+                &readiness,
+                &[],
+                &[],
+            )
+        })
+    }
+
+    fn launch_once(
+        zainod_bin: Option<PathBuf>,
+        port: Port,
+        validator_port: Port,
+        readiness: &ReadinessConfig,
+        extra_args: &[OsString],
+        env: &[(OsString, OsString)],
     ) -> Result<Zainod, LaunchError> {
-        let port = utils::pick_unused_port(listen_port);
         let config_dir = tempfile::tempdir().unwrap();
         let config_file_path = config::zainod(config_dir.path(), port, validator_port).unwrap();
 
@@ -238,72 +422,14 @@ impl Zainod {
             Some(path) => std::process::Command::new(path),
             None => std::process::Command::new("zainod"),
         };
+
         command
-            .args([
-                "--config",
-                format!(
-                    "{}",
-                    config_file_path.to_str().expect("should be valid UTF-8")
-                )
-                .as_str(),
-            ])
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
-
-        let mut handle = command.spawn().unwrap();
-
-        let logs_dir = tempfile::tempdir().unwrap();
-        let stdout_log_path = logs_dir.path().join(STDOUT_LOG);
-        let mut stdout_log = File::create(&stdout_log_path).unwrap();
-        let mut stdout = handle.stdout.take().unwrap();
-        // TODO: consider writing logs in a runtime to increase performance
-        std::thread::spawn(move || {
-            std::io::copy(&mut stdout, &mut stdout_log)
-                .expect("should be able to read/write stdout log");
-        });
-
-        let mut stdout_log = File::open(stdout_log_path).expect("should be able to open log");
-        let mut stdout = String::new();
-
-        let check_interval = std::time::Duration::from_millis(100);
-
-        // wait for stdout log entry that indicates daemon is ready
-        loop {
-            match handle.try_wait() {
-                Ok(Some(exit_status)) => {
-                    stdout_log.read_to_string(&mut stdout).unwrap();
-
-                    let mut stderr = String::new();
-                    handle
-                        .stderr
-                        .take()
-                        .unwrap()
-                        .read_to_string(&mut stderr)
-                        .unwrap();
-
-                    return Err(LaunchError::ProcessFailed {
-                        process_name: "zainod".to_string(),
-                        exit_status,
-                        stdout,
-                        stderr,
-                    });
-                }
-                Ok(None) => (),
-                Err(e) => {
-                    panic!("Unexpected Error: {e}")
-                }
-            };
-
-            stdout_log.read_to_string(&mut stdout).unwrap();
-            if stdout.contains("Error:") {
-                panic!("Zainod launch failed without reporting an error code!\nexiting with panic. you may have to shut the daemon down manually.");
-            } else if stdout.contains("Server Ready.") {
-                // launch successful
-                break;
-            }
+            .arg("--config")
+            .arg(&config_file_path)
+            .args(extra_args)
+            .envs(env.iter().map(|(k, v)| (k, v)));
 
-            std::thread::sleep(check_interval);
-        }
+        let (handle, logs_dir) = process::spawn_and_await(command, "zainod", port, readiness)?;
 
         Ok(Zainod {
             handle,
@@ -317,19 +443,111 @@ impl Zainod {
     pub fn config_path(&self) -> PathBuf {
         self.config_dir.path().join(config::ZAINOD_FILENAME)
     }
+}
 
-    /// Stops the Zcashd process.
-    pub fn stop(&mut self) {
-        self.handle.kill().expect("zainod couldn't be killed")
+/// Builder for launching a [`Zainod`], for callers that need more than [`Zainod::launch`]'s
+/// fixed set of parameters - e.g. extra flags or environment variables this crate doesn't
+/// model yet.
+#[derive(Default)]
+pub struct ZainodBuilder {
+    zainod_bin: Option<PathBuf>,
+    listen_port: Option<Port>,
+    validator_port: Option<Port>,
+    readiness: Option<ReadinessConfig>,
+    extra_args: Vec<OsString>,
+    env: Vec<(OsString, OsString)>,
+}
+
+impl ZainodBuilder {
+    /// Starts a new builder with no binary, fixed port, or extra args set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Path to the `zainod` binary. Defaults to running "zainod" from $PATH.
+    pub fn zainod_bin(mut self, path: impl AsRef<OsStr>) -> Self {
+        self.zainod_bin = Some(PathBuf::from(path.as_ref()));
+        self
+    }
+
+    /// Fixed listen port. Defaults to a randomly picked port.
+    pub fn listen_port(mut self, port: Port) -> Self {
+        self.listen_port = Some(port);
+        self
+    }
+
+    /// Port of the already-running validator to index. Required.
+    pub fn validator_port(mut self, port: Port) -> Self {
+        self.validator_port = Some(port);
+        self
+    }
+
+    /// Overrides how launch detects readiness/failure and how long it waits before giving up.
+    pub fn readiness(mut self, readiness: ReadinessConfig) -> Self {
+        self.readiness = Some(readiness);
+        self
+    }
+
+    /// Appends extra command-line arguments passed to `zainod` as-is, for flags this builder
+    /// doesn't model yet.
+    pub fn extra_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.extra_args
+            .extend(args.into_iter().map(|s| s.as_ref().to_os_string()));
+        self
+    }
+
+    /// Sets an environment variable on the spawned `zainod` process.
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.env
+            .push((key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+        self
+    }
+
+    /// Launches `zainod` with the configured options.
+    ///
+    /// Panics if [`Self::validator_port`] was never set.
+    pub fn spawn(self) -> Result<Zainod, LaunchError> {
+        let readiness = self.readiness.unwrap_or_else(ReadinessConfig::zainod);
+        let validator_port = self
+            .validator_port
+            .expect("validator_port is required to launch zainod");
+        utils::retry_on_port_collision(self.listen_port, |port| {
+            Zainod::launch_once(
+                self.zainod_bin.clone(),
+                port,
+                validator_port,
+                &readiness,
+                &self.extra_args,
+                &self.env,
+            )
+        })
+    }
+}
+
+impl LocalProcess for Zainod {
+    fn handle(&mut self) -> &mut Child {
+        &mut self.handle
+    }
+
+    fn logs_dir(&self) -> &TempDir {
+        &self.logs_dir
+    }
+
+    fn port(&self) -> Port {
+        self.port
+    }
+
+    fn process_name(&self) -> &'static str {
+        "zainod"
     }
 
-    /// Prints the stdout log.
-    pub fn print_stdout(&self) {
-        let stdout_log_path = self.logs_dir.path().join(STDOUT_LOG);
-        let mut stdout_log = File::open(stdout_log_path).expect("should be able to open log");
-        let mut stdout = String::new();
-        stdout_log.read_to_string(&mut stdout).unwrap();
-        println!("{}", stdout);
+    /// Stops the Zainod process.
+    fn stop(&mut self) {
+        self.handle.kill().expect("zainod couldn't be killed")
     }
 }
 
@@ -337,7 +555,7 @@ impl Default for Zainod {
     /// Default launch for Zainod.
     /// Panics on failure.
     fn default() -> Self {
-        Zainod::launch(None, None, 18232).unwrap()
+        Zainod::launch(None, None, 18232, None).unwrap()
     }
 }
 
@@ -346,3 +564,298 @@ impl Drop for Zainod {
         self.stop();
     }
 }
+
+/// Struct associated with a Zebrad process.
+///
+/// Zebra has no CLI and does not mine blocks on its own, so block production for this
+/// validator is driven over RPC rather than a `*-cli generate` call.
+#[derive(Getters)]
+#[getset(get = "pub")]
+pub struct Zebrad {
+    handle: Child,
+    port: Port,
+    _data_dir: DataDir,
+    logs_dir: TempDir,
+    config_dir: TempDir,
+}
+
+impl Zebrad {
+    /// Launches Zebrad process and returns [`crate::Zebrad`] with the handle and associated directories.
+    ///
+    /// Use `zebrad_bin` to specify the path to the binary. If this binary is in $PATH, `None`
+    /// can be specified to run "zebrad".
+    ///
+    /// Use `rpc_port` to specify a port for Zebrad's RPC endpoint. Otherwise, a port is picked
+    /// at random.
+    ///
+    /// Use `activation_heights` to specify custom network upgrade activation heights.
+    ///
+    /// Use `readiness` to customize how launch detects that zebrad is ready (or has failed) and
+    /// how long to wait before giving up. `None` falls back to [`ReadinessConfig::zebrad`].
+    ///
+    /// Use `persistent_data_dir` to point zebrad's cache at a caller-supplied path that is
+    /// reused across launches instead of a throwaway one, so chain state survives between runs.
+    /// The directory is created if it doesn't already exist and is left on disk when the
+    /// returned [`Zebrad`] is dropped - call [`reset_data_dir`] to deliberately wipe it. `None`
+    /// uses a throwaway directory, as before.
+    ///
+    /// When `rpc_port` is `None`, a port collision or stale lock file is retried with a freshly
+    /// picked port up to [`utils::MAX_RANDOM_PORT_RETRIES`] times. With a fixed `rpc_port`,
+    /// [`LaunchError::PortInUse`] / [`LaunchError::LockFileHeld`] are returned directly.
+    pub fn launch(
+        zebrad_bin: Option<PathBuf>,
+        rpc_port: Option<Port>,
+        activation_heights: &ActivationHeights,
+        readiness: Option<ReadinessConfig>,
+        persistent_data_dir: Option<PathBuf>,
+    ) -> Result<Zebrad, LaunchError> {
+        let readiness = readiness.unwrap_or_else(ReadinessConfig::zebrad);
+        utils::retry_on_port_collision(rpc_port, |port| {
+            Self::launch_once(
+                zebrad_bin.clone(),
+                port,
+                activation_heights,
+                &readiness,
+                persistent_data_dir.clone(),
+            )
+        })
+    }
+
+    fn launch_once(
+        zebrad_bin: Option<PathBuf>,
+        port: Port,
+        activation_heights: &ActivationHeights,
+        readiness: &ReadinessConfig,
+        persistent_data_dir: Option<PathBuf>,
+    ) -> Result<Zebrad, LaunchError> {
+        let config_dir = tempfile::tempdir().unwrap();
+        let data_dir = DataDir::for_launch(persistent_data_dir).unwrap();
+        let config_file_path =
+            config::zebrad(config_dir.path(), port, activation_heights, data_dir.path()).unwrap();
+
+        let mut command = match zebrad_bin {
+            Some(path) => std::process::Command::new(path),
+            None => std::process::Command::new("zebrad"),
+        };
+        command.arg("-c").arg(&config_file_path).arg("start");
+
+        let (handle, logs_dir) = process::spawn_and_await(command, "zebrad", port, readiness)?;
+
+        Ok(Zebrad {
+            handle,
+            port,
+            _data_dir: data_dir,
+            logs_dir,
+            config_dir,
+        })
+    }
+
+    /// Returns path to config file.
+    pub fn config_path(&self) -> PathBuf {
+        self.config_dir.path().join(config::ZEBRAD_FILENAME)
+    }
+
+    /// Generates `num_blocks` blocks.
+    ///
+    /// Since Zebra has no `zcash-cli`, blocks are produced through its regtest-only `generate`
+    /// RPC - unlike [`crate::Zcashd::generate_blocks`], which delegates to `zcash-cli generate`.
+    /// Zebra solves the blocks itself, so this crate doesn't need its own miner.
+    pub fn generate_blocks(&self, num_blocks: u32) -> std::io::Result<()> {
+        rpc::call(self.port, "generate", &format!("[{num_blocks}]"))?;
+        Ok(())
+    }
+}
+
+impl LocalProcess for Zebrad {
+    fn handle(&mut self) -> &mut Child {
+        &mut self.handle
+    }
+
+    fn logs_dir(&self) -> &TempDir {
+        &self.logs_dir
+    }
+
+    fn port(&self) -> Port {
+        self.port
+    }
+
+    fn process_name(&self) -> &'static str {
+        "zebrad"
+    }
+
+    /// Stops the Zebrad process.
+    fn stop(&mut self) {
+        if let Err(e) = self.handle.kill() {
+            tracing::warn!("zebrad has already terminated: {e}")
+        };
+        if let Err(e) = self.handle.wait() {
+            tracing::error!("zebrad cannot be awaited: {e}")
+        }
+    }
+}
+
+impl Default for Zebrad {
+    /// Default launch for Zebrad.
+    /// Panics on failure.
+    fn default() -> Self {
+        Zebrad::launch(None, None, &ActivationHeights::default(), None, None).unwrap()
+    }
+}
+
+impl Drop for Zebrad {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Either validator implementation a [`NetworkCluster`] can launch.
+pub enum Validator {
+    Zcashd(Zcashd),
+    Zebrad(Zebrad),
+}
+
+impl Validator {
+    /// Port the validator's RPC endpoint is listening on.
+    pub fn port(&self) -> Port {
+        match self {
+            Validator::Zcashd(zcashd) => *zcashd.port(),
+            Validator::Zebrad(zebrad) => *zebrad.port(),
+        }
+    }
+}
+
+impl LocalProcess for Validator {
+    fn handle(&mut self) -> &mut Child {
+        match self {
+            Validator::Zcashd(zcashd) => zcashd.handle(),
+            Validator::Zebrad(zebrad) => zebrad.handle(),
+        }
+    }
+
+    fn logs_dir(&self) -> &TempDir {
+        match self {
+            Validator::Zcashd(zcashd) => zcashd.logs_dir(),
+            Validator::Zebrad(zebrad) => zebrad.logs_dir(),
+        }
+    }
+
+    fn port(&self) -> Port {
+        Validator::port(self)
+    }
+
+    fn process_name(&self) -> &'static str {
+        match self {
+            Validator::Zcashd(_) => "zcashd",
+            Validator::Zebrad(_) => "zebrad",
+        }
+    }
+
+    fn stop(&mut self) {
+        match self {
+            Validator::Zcashd(zcashd) => LocalProcess::stop(zcashd),
+            Validator::Zebrad(zebrad) => LocalProcess::stop(zebrad),
+        }
+    }
+}
+
+/// Which validator a [`NetworkCluster`] should launch, and how.
+pub enum ValidatorConfig {
+    Zcashd {
+        zcashd_bin: Option<PathBuf>,
+        zcash_cli_bin: Option<PathBuf>,
+        rpc_port: Option<Port>,
+        miner_address: Option<String>,
+        readiness: Option<ReadinessConfig>,
+    },
+    Zebrad {
+        zebrad_bin: Option<PathBuf>,
+        rpc_port: Option<Port>,
+        readiness: Option<ReadinessConfig>,
+    },
+}
+
+/// Launches a validator (either [`Zcashd`] or [`Zebrad`]) together with a [`Zainod`] indexer
+/// wired to it, and tears both down in reverse launch order on drop.
+///
+/// This removes the need to manually launch a validator, read its port back out, and pass it
+/// into the indexer in the right order every time a test needs a full regtest net.
+pub struct NetworkCluster {
+    validator: Validator,
+    indexer: Zainod,
+}
+
+impl NetworkCluster {
+    /// Launches `validator_config`'s validator, then a Zainod indexer pointed at it.
+    pub fn launch(
+        validator_config: ValidatorConfig,
+        activation_heights: &ActivationHeights,
+        zainod_bin: Option<PathBuf>,
+        zainod_listen_port: Option<Port>,
+        zainod_readiness: Option<ReadinessConfig>,
+    ) -> Result<NetworkCluster, LaunchError> {
+        let mut validator = match validator_config {
+            ValidatorConfig::Zcashd {
+                zcashd_bin,
+                zcash_cli_bin,
+                rpc_port,
+                miner_address,
+                readiness,
+            } => Validator::Zcashd(Zcashd::launch(
+                zcashd_bin,
+                zcash_cli_bin,
+                rpc_port,
+                activation_heights,
+                miner_address.as_deref(),
+                readiness,
+            )?),
+            ValidatorConfig::Zebrad {
+                zebrad_bin,
+                rpc_port,
+                readiness,
+            } => Validator::Zebrad(Zebrad::launch(
+                zebrad_bin,
+                rpc_port,
+                activation_heights,
+                readiness,
+                None,
+            )?),
+        };
+
+        let indexer = match Zainod::launch(
+            zainod_bin,
+            zainod_listen_port,
+            validator.port(),
+            zainod_readiness,
+        ) {
+            Ok(indexer) => indexer,
+            Err(e) => {
+                validator.stop();
+                return Err(e);
+            }
+        };
+
+        Ok(NetworkCluster { validator, indexer })
+    }
+
+    /// The running validator.
+    pub fn validator(&self) -> &Validator {
+        &self.validator
+    }
+
+    /// The running Zainod indexer.
+    pub fn indexer(&self) -> &Zainod {
+        &self.indexer
+    }
+
+    /// Stops the indexer, then the validator - the reverse of launch order.
+    pub fn stop(&mut self) {
+        self.indexer.stop();
+        self.validator.stop();
+    }
+}
+
+impl Drop for NetworkCluster {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,14 @@
+//! Network parameters shared by validator configs.
+
+/// Custom network upgrade activation heights, written into generated `zcashd`/`zebrad` configs.
+///
+/// Any field left as `None` falls back to the validator's own default for that upgrade.
+#[derive(Debug, Clone, Default)]
+pub struct ActivationHeights {
+    pub overwinter: Option<u32>,
+    pub sapling: Option<u32>,
+    pub blossom: Option<u32>,
+    pub heartwood: Option<u32>,
+    pub canopy: Option<u32>,
+    pub nu5: Option<u32>,
+}
@@ -0,0 +1,149 @@
+//! Shared plumbing for launching and supervising a local child process.
+
+use std::fs::File;
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+
+use portpicker::Port;
+use tempfile::TempDir;
+
+use crate::error::LaunchError;
+use crate::readiness::ReadinessConfig;
+use crate::{kill_and_wait, utils, STDOUT_LOG};
+
+/// Common behavior of a locally-spawned validator or indexer process.
+///
+/// [`Zcashd`](crate::Zcashd), [`Zainod`](crate::Zainod) and [`Zebrad`](crate::Zebrad) all
+/// implement this so they can be supervised uniformly, e.g. by [`crate::NetworkCluster`].
+pub trait LocalProcess {
+    /// The running child process.
+    fn handle(&mut self) -> &mut Child;
+
+    /// Directory holding the process's captured stdout log.
+    fn logs_dir(&self) -> &TempDir;
+
+    /// Port the process is listening on.
+    fn port(&self) -> Port;
+
+    /// Name used to label this process in error messages and logs.
+    fn process_name(&self) -> &'static str;
+
+    /// Stops the process.
+    fn stop(&mut self);
+
+    /// Prints the captured stdout log.
+    fn print_stdout(&self) {
+        let stdout_log_path = self.logs_dir().path().join(STDOUT_LOG);
+        let mut stdout_log = File::open(stdout_log_path).expect("should be able to open log");
+        let mut stdout = String::new();
+        stdout_log.read_to_string(&mut stdout).unwrap();
+        println!("{}", stdout);
+    }
+
+    /// Blocks until the process reports readiness, a fatal error, or `readiness.timeout` passes.
+    fn wait_for_ready(&mut self, readiness: &ReadinessConfig) -> Result<(), LaunchError> {
+        let logs_dir_path = self.logs_dir().path().join(STDOUT_LOG);
+        let process_name = self.process_name();
+        let port = self.port();
+        let handle = self.handle();
+        poll_until_ready(handle, &logs_dir_path, process_name, port, readiness)
+    }
+}
+
+/// Spawns `command` (which must not already set stdout/stderr), captures its stdout to a log
+/// file in a fresh [`TempDir`], and blocks until `readiness` is satisfied.
+///
+/// Returns the running handle and its log directory once the process is ready, or a
+/// [`LaunchError`] if it exited, reported a fatal error line, or timed out.
+pub(crate) fn spawn_and_await(
+    mut command: Command,
+    process_name: &'static str,
+    port: Port,
+    readiness: &ReadinessConfig,
+) -> Result<(Child, TempDir), LaunchError> {
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+
+    let mut handle = command.spawn().unwrap();
+
+    let logs_dir = tempfile::tempdir().unwrap();
+    let stdout_log_path = logs_dir.path().join(STDOUT_LOG);
+    let mut stdout_log = File::create(&stdout_log_path).unwrap();
+    let mut stdout = handle.stdout.take().unwrap();
+    // TODO: consider writing logs in a runtime to increase performance
+    std::thread::spawn(move || {
+        std::io::copy(&mut stdout, &mut stdout_log)
+            .expect("should be able to read/write stdout log");
+    });
+
+    poll_until_ready(&mut handle, &stdout_log_path, process_name, port, readiness)?;
+
+    Ok((handle, logs_dir))
+}
+
+/// Polls `stdout_log_path` until `readiness.ready_pattern` matches, `readiness.error_pattern`
+/// matches, `handle` exits, or `readiness.timeout` elapses.
+fn poll_until_ready(
+    handle: &mut Child,
+    stdout_log_path: &std::path::Path,
+    process_name: &'static str,
+    port: Port,
+    readiness: &ReadinessConfig,
+) -> Result<(), LaunchError> {
+    let mut stdout_log = File::open(stdout_log_path).expect("should be able to open log");
+    let mut stdout = String::new();
+
+    let check_interval = std::time::Duration::from_millis(100);
+    let launch_deadline = std::time::Instant::now() + readiness.timeout;
+
+    loop {
+        match handle.try_wait() {
+            Ok(Some(exit_status)) => {
+                stdout_log.read_to_string(&mut stdout).unwrap();
+                let stderr = read_stderr(handle);
+                return Err(utils::classify_launch_failure(
+                    process_name,
+                    port,
+                    exit_status,
+                    stdout,
+                    stderr,
+                ));
+            }
+            Ok(None) => (),
+            Err(e) => panic!("Unexpected Error: {e}"),
+        };
+
+        stdout_log.read_to_string(&mut stdout).unwrap();
+        if readiness.error_pattern.is_match(&stdout) {
+            let exit_status = kill_and_wait(handle, process_name);
+            let stderr = read_stderr(handle);
+            return Err(utils::classify_launch_failure(
+                process_name,
+                port,
+                exit_status,
+                stdout,
+                stderr,
+            ));
+        } else if readiness.ready_pattern.is_match(&stdout) {
+            return Ok(());
+        } else if std::time::Instant::now() >= launch_deadline {
+            kill_and_wait(handle, process_name);
+            let stderr = read_stderr(handle);
+            return Err(LaunchError::Timeout {
+                process_name: process_name.to_string(),
+                timeout: readiness.timeout,
+                stdout,
+                stderr,
+            });
+        }
+
+        std::thread::sleep(check_interval);
+    }
+}
+
+fn read_stderr(handle: &mut Child) -> String {
+    let mut stderr = String::new();
+    if let Some(mut stderr_handle) = handle.stderr.take() {
+        stderr_handle.read_to_string(&mut stderr).ok();
+    }
+    stderr
+}
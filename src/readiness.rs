@@ -0,0 +1,60 @@
+//! Configurable "is this process ready yet" detection used while launching a process.
+
+use std::time::Duration;
+
+use regex::Regex;
+
+/// Default time allotted for a process to report readiness before launch gives up.
+///
+/// Mirrors the acceptance harness's own `LAUNCH_DELAY`.
+const DEFAULT_LAUNCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Describes how to recognize, from a process's captured stdout, that it has finished
+/// starting up (or that it has failed outright), and how long to wait before giving up.
+#[derive(Debug, Clone)]
+pub struct ReadinessConfig {
+    /// Matched against stdout; a match means the process is ready to use.
+    pub ready_pattern: Regex,
+    /// Matched against stdout; a match means the process reported a fatal error.
+    pub error_pattern: Regex,
+    /// Overall deadline for the ready pattern to appear.
+    pub timeout: Duration,
+}
+
+impl ReadinessConfig {
+    /// Builds a custom readiness config.
+    pub fn new(ready_pattern: Regex, error_pattern: Regex, timeout: Duration) -> Self {
+        Self {
+            ready_pattern,
+            error_pattern,
+            timeout,
+        }
+    }
+
+    /// Readiness config matching `zcashd`'s own log output.
+    pub fn zcashd() -> Self {
+        Self {
+            ready_pattern: Regex::new("init message: Done loading").unwrap(),
+            error_pattern: Regex::new("Error:").unwrap(),
+            timeout: DEFAULT_LAUNCH_TIMEOUT,
+        }
+    }
+
+    /// Readiness config matching `zainod`'s own log output.
+    pub fn zainod() -> Self {
+        Self {
+            ready_pattern: Regex::new("Server Ready.").unwrap(),
+            error_pattern: Regex::new("Error:").unwrap(),
+            timeout: DEFAULT_LAUNCH_TIMEOUT,
+        }
+    }
+
+    /// Readiness config matching `zebrad`'s own log output.
+    pub fn zebrad() -> Self {
+        Self {
+            ready_pattern: Regex::new("Opened RPC endpoint").unwrap(),
+            error_pattern: Regex::new("Error:").unwrap(),
+            timeout: DEFAULT_LAUNCH_TIMEOUT,
+        }
+    }
+}
@@ -0,0 +1,57 @@
+//! Minimal blocking JSON-RPC client used to talk to a validator's RPC endpoint directly,
+//! for validators (like `zebrad`) that have no CLI of their own.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use portpicker::Port;
+
+/// Calls `method` with the raw JSON `params` array against the RPC endpoint on `port`,
+/// returning the raw JSON response body.
+pub(crate) fn call(port: Port, method: &str, params: &str) -> std::io::Result<String> {
+    let body = format!(
+        "{{\"jsonrpc\":\"1.0\",\"id\":\"zcash-local-net\",\"method\":\"{method}\",\"params\":{params}}}"
+    );
+    let request = format!(
+        "POST / HTTP/1.1\r\n\
+         Host: 127.0.0.1:{port}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n\
+         {body}",
+        body.len()
+    );
+
+    let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+    stream.write_all(request.as_bytes())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    // Strip the HTTP headers, keeping only the JSON body.
+    let body = match response.split_once("\r\n\r\n") {
+        Some((_headers, body)) => body,
+        None => &response,
+    };
+
+    check_error(body)?;
+    Ok(body.to_string())
+}
+
+/// Fails with the response's `error` field if the JSON-RPC call was rejected, e.g.
+/// `{"result":null,"error":{"code":-1,"message":"rejected"}}`. A response with no `error` field
+/// at all is treated the same as `"error":null` - some servers omit it on success.
+fn check_error(response: &str) -> std::io::Result<()> {
+    let needle = "\"error\":";
+    let Some(start) = response.find(needle).map(|i| i + needle.len()) else {
+        return Ok(());
+    };
+
+    if response[start..].trim_start().starts_with("null") {
+        return Ok(());
+    }
+
+    Err(std::io::Error::other(format!(
+        "RPC call rejected: {response}"
+    )))
+}
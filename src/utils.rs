@@ -0,0 +1,74 @@
+//! Small helpers shared by the process launchers.
+
+use portpicker::Port;
+
+use crate::error::LaunchError;
+
+/// Maximum number of times launch retries with a freshly picked port after a port collision,
+/// when the caller asked for a random port.
+pub(crate) const MAX_RANDOM_PORT_RETRIES: u32 = 3;
+
+/// Returns `port` if specified, otherwise picks an unused port at random.
+pub(crate) fn pick_unused_port(port: Option<Port>) -> Port {
+    match port {
+        Some(port) => port,
+        None => portpicker::pick_unused_port().expect("should find an unused port"),
+    }
+}
+
+/// Runs `attempt` with a port, retrying with a freshly picked port on a port collision or a
+/// held lock file, up to [`MAX_RANDOM_PORT_RETRIES`] times - but only when `fixed_port` is
+/// `None`. A caller-supplied fixed port is never silently swapped out from under it.
+pub(crate) fn retry_on_port_collision<T>(
+    fixed_port: Option<Port>,
+    mut attempt: impl FnMut(Port) -> Result<T, LaunchError>,
+) -> Result<T, LaunchError> {
+    let mut attempts = 0;
+    loop {
+        let port = pick_unused_port(fixed_port);
+        match attempt(port) {
+            Ok(value) => return Ok(value),
+            Err(LaunchError::PortInUse { .. } | LaunchError::LockFileHeld { .. })
+                if fixed_port.is_none() && attempts < MAX_RANDOM_PORT_RETRIES =>
+            {
+                attempts += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Looks at a failed launch's captured output and classifies it as a port collision or a held
+/// lock file when possible, falling back to a generic [`LaunchError::ProcessFailed`].
+pub(crate) fn classify_launch_failure(
+    process_name: &str,
+    port: Port,
+    exit_status: std::process::ExitStatus,
+    stdout: String,
+    stderr: String,
+) -> LaunchError {
+    let combined = format!("{stdout}\n{stderr}");
+    let lower = combined.to_lowercase();
+
+    if lower.contains("already in use") || lower.contains("address already in use") {
+        LaunchError::PortInUse {
+            process_name: process_name.to_string(),
+            port,
+        }
+    } else if lower.contains("lock file")
+        || lower.contains("lock_held")
+        || lower.contains("lock on data directory")
+        || lower.contains("probably already running")
+    {
+        LaunchError::LockFileHeld {
+            process_name: process_name.to_string(),
+        }
+    } else {
+        LaunchError::ProcessFailed {
+            process_name: process_name.to_string(),
+            exit_status,
+            stdout,
+            stderr,
+        }
+    }
+}